@@ -0,0 +1,52 @@
+//! free-fly camera: position + yaw/pitch, used to build the view matrix
+
+use crate::math::{Mat4, Vec3};
+
+/// keep pitch away from the poles so look_at's up vector never flips
+const PITCH_LIMIT: f32 = std::f32::consts::FRAC_PI_2 - 0.01;
+
+/// fly camera controlled by yaw (turn left/right) and pitch (look up/down)
+pub struct Camera {
+    pub pos: Vec3,
+    pub yaw: f32,
+    pub pitch: f32,
+}
+
+impl Camera {
+    pub fn new(pos: Vec3) -> Self {
+        Camera { pos, yaw: 0.0, pitch: 0.0 }
+    }
+
+    /// unit vector the camera is looking along
+    pub fn forward(&self) -> Vec3 {
+        Vec3::new(
+            self.pitch.cos() * self.yaw.sin(),
+            self.pitch.sin(),
+            -self.pitch.cos() * self.yaw.cos(),
+        )
+    }
+
+    /// unit vector pointing to the camera's right (for strafing)
+    pub fn right(&self) -> Vec3 {
+        self.forward().cross(Vec3::Y).normalize()
+    }
+
+    /// unit vector pointing to the camera's true up (perpendicular to forward and right)
+    pub fn up(&self) -> Vec3 {
+        self.right().cross(self.forward())
+    }
+
+    pub fn add_yaw(&mut self, delta: f32) {
+        self.yaw += delta;
+    }
+
+    pub fn add_pitch(&mut self, delta: f32) {
+        self.pitch = (self.pitch + delta).clamp(-PITCH_LIMIT, PITCH_LIMIT);
+    }
+
+    /// view matrix for this camera (world space -> camera space)
+    pub fn view_matrix(&self) -> Mat4 {
+        let target = self.pos + self.forward();
+        Mat4::look_at(self.pos, target, Vec3::Y)
+    }
+}
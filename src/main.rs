@@ -19,211 +19,16 @@
 use std::f32::consts::PI;
 use minifb::{Key, Window, WindowOptions};
 
-/// simple 3d vector
-#[derive(Clone, Copy, Debug)]
-struct Vec3 {
-    x: f32,
-    y: f32,
-    z: f32,
-}
-
-impl Vec3 {
-    fn new(x: f32, y: f32, z: f32) -> Self {
-        Vec3 { x, y, z }
-    }
-}
-
-/// 4x4 matrix for 3d transforms
-#[derive(Clone, Copy, Debug)]
-struct Mat4 {
-    m: [f32; 16],
-}
-
-impl Mat4 {
-    fn identity() -> Self {
-        Mat4 {
-            m: [
-                1.0, 0.0, 0.0, 0.0,
-                0.0, 1.0, 0.0, 0.0,
-                0.0, 0.0, 1.0, 0.0,
-                0.0, 0.0, 0.0, 1.0,
-            ],
-        }
-    }
-
-    /// matrix multiply: self * other
-    fn mul(&self, other: &Mat4) -> Mat4 {
-        let mut result = Mat4 { m: [0.0; 16] };
-        for row in 0..4 {
-            for col in 0..4 {
-                result.m[row * 4 + col] =
-                    self.m[row * 4 + 0] * other.m[0 * 4 + col] +
-                    self.m[row * 4 + 1] * other.m[1 * 4 + col] +
-                    self.m[row * 4 + 2] * other.m[2 * 4 + col] +
-                    self.m[row * 4 + 3] * other.m[3 * 4 + col];
-            }
-        }
-        result
-    }
-
-    /// make x-axis rotation matrix
-    fn rotation_x(angle: f32) -> Mat4 {
-        let mut m = Mat4::identity();
-        let c = angle.cos();
-        let s = angle.sin();
-        m.m[5] = c;    // (1,1)
-        m.m[6] = -s;   // (1,2)
-        m.m[9] = s;    // (2,1)
-        m.m[10] = c;   // (2,2)
-        m
-    }
-
-    /// make y-axis rotation matrix
-    fn rotation_y(angle: f32) -> Mat4 {
-        let mut m = Mat4::identity();
-        let c = angle.cos();
-        let s = angle.sin();
-        m.m[0] = c;    // (0,0)
-        m.m[2] = s;    // (0,2)
-        m.m[8] = -s;   // (2,0)
-        m.m[10] = c;   // (2,2)
-        m
-    }
-
-    /// make perspective matrix
-    ///
-    /// fov: field-of-view in radians
-    /// aspect: width / height
-    /// near: near plane
-    /// far: far plane
-    fn perspective(fov: f32, aspect: f32, near: f32, far: f32) -> Mat4 {
-        let mut m = Mat4::identity();
-        let f = 1.0 / (fov / 2.0).tan();
-        m.m[0] = f / aspect;
-        m.m[5] = f;
-        m.m[10] = (far + near) / (near - far);
-        m.m[11] = -1.0;
-        m.m[14] = (2.0 * far * near) / (near - far);
-        m.m[15] = 0.0;
-        m
-    }
-
-    /// transform vec3 by matrix (w=1)
-    fn transform_vec3(&self, v: Vec3) -> Vec3 {
-        let x = v.x * self.m[0] + v.y * self.m[4] + v.z * self.m[8] + self.m[12];
-        let y = v.x * self.m[1] + v.y * self.m[5] + v.z * self.m[9] + self.m[13];
-        let z = v.x * self.m[2] + v.y * self.m[6] + v.z * self.m[10] + self.m[14];
-        let w = v.x * self.m[3] + v.y * self.m[7] + v.z * self.m[11] + self.m[15];
-        if w != 0.0 {
-            Vec3::new(x / w, y / w, z / w)
-        } else {
-            Vec3::new(x, y, z)
-        }
-    }
-}
-
-/// draw line in pixel buffer using bresenham
-/// https://www.youtube.com/watch?v=CceepU1vIKo
-/// color: 0xRRGGBB int (e.g. 0xffffff for white)
-fn draw_line(
-    buffer: &mut [u32],
-    width: usize,
-    height: usize,
-    x0: i32,
-    y0: i32,
-    x1: i32,
-    y1: i32,
-    color: u32,
-) {
-    let mut dx = (x1 - x0).abs();
-    let sx = if x0 < x1 { 1 } else { -1 };
-    let mut dy = -(y1 - y0).abs();
-    let sy = if y0 < y1 { 1 } else { -1 };
-    let mut err = dx + dy;
-
-    let (mut x, mut y) = (x0, y0);
-
-    loop {
-        if x >= 0 && x < width as i32 && y >= 0 && y < height as i32 {
-            buffer[y as usize * width + x as usize] = color;
-        }
-        if x == x1 && y == y1 {
-            break;
-        }
-        let e2 = 2 * err;
-        if e2 >= dy {
-            err += dy;
-            x += sx;
-        }
-        if e2 <= dx {
-            err += dx;
-            y += sy;
-        }
-    }
-}
-
-/// mesh data with vertices and edges
-struct Mesh {
-    vertices: Vec<Vec3>,
-    edges: Vec<(usize, usize)>,
-}
-
-/// make cube mesh
-fn create_cube() -> Mesh {
-    Mesh {
-        vertices: vec![
-            Vec3::new(-1.0, -1.0, -1.0),
-            Vec3::new( 1.0, -1.0, -1.0),
-            Vec3::new( 1.0,  1.0, -1.0),
-            Vec3::new(-1.0,  1.0, -1.0),
-            Vec3::new(-1.0, -1.0,  1.0),
-            Vec3::new( 1.0, -1.0,  1.0),
-            Vec3::new( 1.0,  1.0,  1.0),
-            Vec3::new(-1.0,  1.0,  1.0),
-        ],
-        edges: vec![
-            (0, 1), (1, 2), (2, 3), (3, 0), // bottom
-            (4, 5), (5, 6), (6, 7), (7, 4), // top
-            (0, 4), (1, 5), (2, 6), (3, 7), // sides
-        ],
-    }
-}
-
-/// make pyramid mesh
-fn create_pyramid() -> Mesh {
-    Mesh {
-        vertices: vec![
-            Vec3::new( 0.0,  1.0,  0.0),    // top
-            Vec3::new(-1.0, -1.0, -1.0),    // base
-            Vec3::new( 1.0, -1.0, -1.0),
-            Vec3::new( 1.0, -1.0,  1.0),
-            Vec3::new(-1.0, -1.0,  1.0),
-        ],
-        edges: vec![
-            (1, 2), (2, 3), (3, 4), (4, 1), // base
-            (0, 1), (0, 2), (0, 3), (0, 4), // sides
-        ],
-    }
-}
-
-/// make octahedron mesh
-fn create_octahedron() -> Mesh {
-    Mesh {
-        vertices: vec![
-            Vec3::new( 0.0,  1.0,  0.0),    // top
-            Vec3::new( 0.0, -1.0,  0.0),    // bottom
-            Vec3::new(-1.0,  0.0,  0.0),    // middle points
-            Vec3::new( 1.0,  0.0,  0.0),
-            Vec3::new( 0.0,  0.0, -1.0),
-            Vec3::new( 0.0,  0.0,  1.0),
-        ],
-        edges: vec![
-            (0, 2), (0, 3), (0, 4), (0, 5), // top edges
-            (1, 2), (1, 3), (1, 4), (1, 5), // bottom edges
-            (2, 4), (4, 3), (3, 5), (5, 2), // middle edges
-        ],
-    }
-}
+mod camera;
+mod math;
+mod mesh;
+mod render;
+mod sdf;
+use camera::Camera;
+use math::{Mat4, Vec3};
+use mesh::{create_cube, create_octahedron, create_pyramid, Mesh};
+use render::{draw_sdf, draw_solid, draw_wireframe};
+use sdf::{RoundBox, Sphere, Subtraction, Union};
 
 fn main() {
     // window size
@@ -232,7 +37,7 @@ fn main() {
 
     // create window using minifb
     let mut window = Window::new(
-        "3d shapes (1-3 to switch, esc to exit)",
+        "3d shapes (1-4 to switch, tab for wireframe/solid, space for sdf mode, o for ortho, esc to exit)",
         width,
         height,
         WindowOptions::default(),
@@ -244,36 +49,130 @@ fn main() {
     // limit to ~60 fps
     window.limit_update_rate(Some(std::time::Duration::from_micros(16600)));
 
-    // make framebuffer
+    // make framebuffer plus a depth buffer for solid rendering
     let mut buffer = vec![0u32; width * height];
+    let mut depth_buffer = vec![f32::NEG_INFINITY; width * height];
 
-    // setup projection matrix
+    // setup projection matrices: perspective is the default, orthographic is a
+    // toggle (see Key::O below) for inspecting a model's true proportions
     let aspect_ratio = width as f32 / height as f32;
     let fov = PI / 3.0; // 60 degrees
-    let projection = Mat4::perspective(fov, aspect_ratio, 0.1, 100.0);
-
-    // create meshes
-    let meshes = [
+    let perspective_projection = Mat4::perspective(fov, aspect_ratio, 0.1, 100.0);
+    let ortho_scale = 2.5;
+    let orthographic_projection = Mat4::orthographic(
+        -ortho_scale * aspect_ratio, ortho_scale * aspect_ratio,
+        -ortho_scale, ortho_scale,
+        0.1, 100.0,
+    );
+
+    // create meshes, optionally adding a user-supplied obj file as a fourth slot
+    let mut meshes = vec![
         create_cube(),
         create_pyramid(),
         create_octahedron(),
     ];
-    
+
+    if let Some(path) = std::env::args().nth(1) {
+        match Mesh::from_obj(&path) {
+            Ok(loaded) => meshes.push(loaded),
+            Err(e) => eprintln!("failed to load obj file '{}': {}", path, e),
+        }
+    }
+
     let mut current_mesh = 0;
 
     let mut angle = 0.0;
 
+    // camera starts 5 units back from the origin, looking down -z (same vantage
+    // point the old hard-coded "subtract 5 from z" hack used)
+    let mut camera = Camera::new(Vec3::new(0.0, 0.0, 5.0));
+    let move_speed = 0.08;
+    let look_speed = 0.03;
+
+    // fixed directional light, pointing down and towards the camera's starting spot
+    let light_dir = Vec3::new(0.4, -0.6, 0.7).normalize();
+
+    let mut solid = true;
+    let mut tab_was_down = false;
+
+    // sdf demo scene: a sphere unioned with a rounded box that has a sphere
+    // carved out of it
+    let sdf_scene = Union(
+        Sphere { center: Vec3::new(-1.3, 0.0, 0.0), radius: 1.0 },
+        Subtraction(
+            RoundBox { center: Vec3::new(1.3, 0.0, 0.0), half_extents: Vec3::new(0.8, 0.8, 0.8), radius: 0.1 },
+            Sphere { center: Vec3::new(1.3, 0.0, 0.9), radius: 0.6 },
+        ),
+    );
+    let mut use_sdf = false;
+    let mut space_was_down = false;
+
+    let mut use_ortho = false;
+    let mut o_was_down = false;
+
+    // one rasterizer thread per available core, falling back to single-threaded
+    // if the core count can't be determined
+    let render_threads = std::thread::available_parallelism().map(|n| n.get()).unwrap_or(1);
+
     // main loop
     while window.is_open() && !window.is_key_down(Key::Escape) {
         // handle mesh switching
         if window.is_key_down(Key::Key1) { current_mesh = 0; }
         if window.is_key_down(Key::Key2) { current_mesh = 1; }
         if window.is_key_down(Key::Key3) { current_mesh = 2; }
+        if window.is_key_down(Key::Key4) && meshes.len() > 3 { current_mesh = 3; }
+
+        // tab toggles wireframe vs. solid rendering (edge-triggered so it doesn't
+        // flip back and forth every frame the key is held)
+        let tab_is_down = window.is_key_down(Key::Tab);
+        if tab_is_down && !tab_was_down {
+            solid = !solid;
+        }
+        tab_was_down = tab_is_down;
 
-        // clear to black
+        // space switches between the mesh backend and the raymarched sdf backend
+        let space_is_down = window.is_key_down(Key::Space);
+        if space_is_down && !space_was_down {
+            use_sdf = !use_sdf;
+        }
+        space_was_down = space_is_down;
+
+        // o toggles perspective vs. orthographic projection
+        let o_is_down = window.is_key_down(Key::O);
+        if o_is_down && !o_was_down {
+            use_ortho = !use_ortho;
+        }
+        o_was_down = o_is_down;
+
+        // WASD moves the camera along its own forward/right axes
+        let forward = camera.forward();
+        let right = camera.right();
+        if window.is_key_down(Key::W) {
+            camera.pos = camera.pos + forward * move_speed;
+        }
+        if window.is_key_down(Key::S) {
+            camera.pos = camera.pos - forward * move_speed;
+        }
+        if window.is_key_down(Key::A) {
+            camera.pos = camera.pos - right * move_speed;
+        }
+        if window.is_key_down(Key::D) {
+            camera.pos = camera.pos + right * move_speed;
+        }
+
+        // arrow keys look around
+        if window.is_key_down(Key::Left) { camera.add_yaw(-look_speed); }
+        if window.is_key_down(Key::Right) { camera.add_yaw(look_speed); }
+        if window.is_key_down(Key::Up) { camera.add_pitch(look_speed); }
+        if window.is_key_down(Key::Down) { camera.add_pitch(-look_speed); }
+
+        // clear color and depth buffers
         for pixel in buffer.iter_mut() {
             *pixel = 0x000000;
         }
+        for d in depth_buffer.iter_mut() {
+            *d = f32::NEG_INFINITY;
+        }
 
         // make rotation matrices
         let rx = Mat4::rotation_x(angle * 1.3);
@@ -281,34 +180,20 @@ fn main() {
 
         // combine transforms
         let model = rx.mul(&ry);
+        let view = camera.view_matrix();
+        let projection = if use_ortho { &orthographic_projection } else { &perspective_projection };
 
-        // get current mesh
-        let mesh = &meshes[current_mesh];
-
-        // draw mesh edges
-        for &(i0, i1) in &mesh.edges {
-            let v0 = mesh.vertices[i0];
-            let v1 = mesh.vertices[i1];
-
-            // rotate and move to camera space
-            let v0_transformed = model.transform_vec3(v0);
-            let v1_transformed = model.transform_vec3(v1);
-
-            let v0_in_world = Vec3::new(v0_transformed.x, v0_transformed.y, v0_transformed.z - 5.0);
-            let v1_in_world = Vec3::new(v1_transformed.x, v1_transformed.y, v1_transformed.z - 5.0);
-
-            // project to screen
-            let p0 = projection.transform_vec3(v0_in_world);
-            let p1 = projection.transform_vec3(v1_in_world);
-
-            // convert to screen coords
-            let x0 = ((p0.x + 1.0) * 0.5 * width as f32) as i32;
-            let y0 = ((1.0 - p0.y) * 0.5 * height as f32) as i32;
-            let x1 = ((p1.x + 1.0) * 0.5 * width as f32) as i32;
-            let y1 = ((1.0 - p1.y) * 0.5 * height as f32) as i32;
+        if use_sdf {
+            draw_sdf(&mut buffer, width, height, &sdf_scene, &camera, fov, aspect_ratio, light_dir, 0xff8844);
+        } else {
+            // get current mesh
+            let mesh = &meshes[current_mesh];
 
-            // draw edge
-            draw_line(&mut buffer, width, height, x0, y0, x1, y1, 0xffffff);
+            if solid {
+                draw_solid(&mut buffer, &mut depth_buffer, width, height, mesh, &model, &view, projection, light_dir, 0x3399ff, render_threads);
+            } else {
+                draw_wireframe(&mut buffer, width, height, mesh, &model, &view, projection, 0xffffff);
+            }
         }
 
         // update screen
@@ -0,0 +1,260 @@
+//! vector/matrix math used throughout the renderer: camera, lighting,
+//! culling and projection all build on this
+//!
+//! this is a foundational module, so not every constant/method below has a
+//! caller yet - allow dead_code rather than trim the api down to just today's uses
+#![allow(dead_code)]
+
+use std::ops::{Add, Mul, Neg, Sub};
+
+/// simple 3d vector
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Vec3 {
+    pub(crate) x: f32,
+    pub(crate) y: f32,
+    pub(crate) z: f32,
+}
+
+impl Vec3 {
+    pub(crate) const ZERO: Vec3 = Vec3::new(0.0, 0.0, 0.0);
+    pub(crate) const ONE: Vec3 = Vec3::new(1.0, 1.0, 1.0);
+    pub(crate) const X: Vec3 = Vec3::new(1.0, 0.0, 0.0);
+    pub(crate) const Y: Vec3 = Vec3::new(0.0, 1.0, 0.0);
+    pub(crate) const Z: Vec3 = Vec3::new(0.0, 0.0, 1.0);
+
+    pub(crate) const fn new(x: f32, y: f32, z: f32) -> Self {
+        Vec3 { x, y, z }
+    }
+
+    pub(crate) fn add(&self, other: Vec3) -> Vec3 {
+        Vec3::new(self.x + other.x, self.y + other.y, self.z + other.z)
+    }
+
+    pub(crate) fn sub(&self, other: Vec3) -> Vec3 {
+        Vec3::new(self.x - other.x, self.y - other.y, self.z - other.z)
+    }
+
+    pub(crate) fn scale(&self, s: f32) -> Vec3 {
+        Vec3::new(self.x * s, self.y * s, self.z * s)
+    }
+
+    pub(crate) fn dot(&self, other: Vec3) -> f32 {
+        self.x * other.x + self.y * other.y + self.z * other.z
+    }
+
+    pub(crate) fn cross(&self, other: Vec3) -> Vec3 {
+        Vec3::new(
+            self.y * other.z - self.z * other.y,
+            self.z * other.x - self.x * other.z,
+            self.x * other.y - self.y * other.x,
+        )
+    }
+
+    pub(crate) fn length(&self) -> f32 {
+        self.dot(*self).sqrt()
+    }
+
+    pub(crate) fn normalize(&self) -> Vec3 {
+        self.scale(1.0 / self.length())
+    }
+}
+
+impl Add for Vec3 {
+    type Output = Vec3;
+    fn add(self, other: Vec3) -> Vec3 {
+        Vec3::add(&self, other)
+    }
+}
+
+impl Sub for Vec3 {
+    type Output = Vec3;
+    fn sub(self, other: Vec3) -> Vec3 {
+        Vec3::sub(&self, other)
+    }
+}
+
+impl Mul<f32> for Vec3 {
+    type Output = Vec3;
+    fn mul(self, s: f32) -> Vec3 {
+        self.scale(s)
+    }
+}
+
+impl Neg for Vec3 {
+    type Output = Vec3;
+    fn neg(self) -> Vec3 {
+        self.scale(-1.0)
+    }
+}
+
+/// 4x4 matrix for 3d transforms
+#[derive(Clone, Copy, Debug)]
+pub(crate) struct Mat4 {
+    m: [f32; 16],
+}
+
+impl Mat4 {
+    pub(crate) fn identity() -> Self {
+        Mat4 {
+            m: [
+                1.0, 0.0, 0.0, 0.0,
+                0.0, 1.0, 0.0, 0.0,
+                0.0, 0.0, 1.0, 0.0,
+                0.0, 0.0, 0.0, 1.0,
+            ],
+        }
+    }
+
+    /// matrix multiply: self * other
+    pub(crate) fn mul(&self, other: &Mat4) -> Mat4 {
+        let mut result = Mat4 { m: [0.0; 16] };
+        for row in 0..4 {
+            for col in 0..4 {
+                result.m[row * 4 + col] =
+                    self.m[row * 4 + 0] * other.m[0 * 4 + col] +
+                    self.m[row * 4 + 1] * other.m[1 * 4 + col] +
+                    self.m[row * 4 + 2] * other.m[2 * 4 + col] +
+                    self.m[row * 4 + 3] * other.m[3 * 4 + col];
+            }
+        }
+        result
+    }
+
+    /// make x-axis rotation matrix
+    pub(crate) fn rotation_x(angle: f32) -> Mat4 {
+        let mut m = Mat4::identity();
+        let c = angle.cos();
+        let s = angle.sin();
+        m.m[5] = c;    // (1,1)
+        m.m[6] = -s;   // (1,2)
+        m.m[9] = s;    // (2,1)
+        m.m[10] = c;   // (2,2)
+        m
+    }
+
+    /// make y-axis rotation matrix
+    pub(crate) fn rotation_y(angle: f32) -> Mat4 {
+        let mut m = Mat4::identity();
+        let c = angle.cos();
+        let s = angle.sin();
+        m.m[0] = c;    // (0,0)
+        m.m[2] = s;    // (0,2)
+        m.m[8] = -s;   // (2,0)
+        m.m[10] = c;   // (2,2)
+        m
+    }
+
+    /// make z-axis rotation matrix
+    pub(crate) fn rotation_z(angle: f32) -> Mat4 {
+        let mut m = Mat4::identity();
+        let c = angle.cos();
+        let s = angle.sin();
+        m.m[0] = c;    // (0,0)
+        m.m[1] = -s;   // (0,1)
+        m.m[4] = s;    // (1,0)
+        m.m[5] = c;    // (1,1)
+        m
+    }
+
+    /// make a translation matrix
+    pub(crate) fn translation(t: Vec3) -> Mat4 {
+        let mut m = Mat4::identity();
+        m.m[12] = t.x;
+        m.m[13] = t.y;
+        m.m[14] = t.z;
+        m
+    }
+
+    /// make a non-uniform scale matrix
+    pub(crate) fn scale(s: Vec3) -> Mat4 {
+        let mut m = Mat4::identity();
+        m.m[0] = s.x;
+        m.m[5] = s.y;
+        m.m[10] = s.z;
+        m
+    }
+
+    /// build a view matrix looking from `eye` to `target`, with `up` defining which
+    /// way is up. the camera's right/up/-forward axes fill columns 0-2 of the rotation
+    /// part (transform_vec3 multiplies a row vector, so each output coordinate is the
+    /// dot product with one axis), and the translation terms fold the eye position
+    /// into camera space.
+    pub(crate) fn look_at(eye: Vec3, target: Vec3, up: Vec3) -> Mat4 {
+        let f = target.sub(eye).normalize();
+        let r = f.cross(up).normalize();
+        let u = r.cross(f);
+
+        let mut m = Mat4::identity();
+        m.m[0] = r.x;
+        m.m[4] = r.y;
+        m.m[8] = r.z;
+        m.m[12] = -r.dot(eye);
+
+        m.m[1] = u.x;
+        m.m[5] = u.y;
+        m.m[9] = u.z;
+        m.m[13] = -u.dot(eye);
+
+        m.m[2] = -f.x;
+        m.m[6] = -f.y;
+        m.m[10] = -f.z;
+        m.m[14] = f.dot(eye);
+
+        m
+    }
+
+    /// make perspective matrix
+    ///
+    /// fov: field-of-view in radians
+    /// aspect: width / height
+    /// near: near plane
+    /// far: far plane
+    pub(crate) fn perspective(fov: f32, aspect: f32, near: f32, far: f32) -> Mat4 {
+        let mut m = Mat4::identity();
+        let f = 1.0 / (fov / 2.0).tan();
+        m.m[0] = f / aspect;
+        m.m[5] = f;
+        m.m[10] = (far + near) / (near - far);
+        m.m[11] = -1.0;
+        m.m[14] = (2.0 * far * near) / (near - far);
+        m.m[15] = 0.0;
+        m
+    }
+
+    /// make orthographic projection matrix: parallel edges stay parallel and size
+    /// no longer shrinks with distance, unlike `perspective`
+    pub(crate) fn orthographic(left: f32, right: f32, bottom: f32, top: f32, near: f32, far: f32) -> Mat4 {
+        let mut m = Mat4::identity();
+        m.m[0] = 2.0 / (right - left);
+        m.m[5] = 2.0 / (top - bottom);
+        m.m[10] = -2.0 / (far - near);
+        m.m[12] = -(right + left) / (right - left);
+        m.m[13] = -(top + bottom) / (top - bottom);
+        m.m[14] = -(far + near) / (far - near);
+        m.m[15] = 1.0;
+        m
+    }
+
+    /// transform vec3 by matrix (w=1)
+    pub(crate) fn transform_vec3(&self, v: Vec3) -> Vec3 {
+        let x = v.x * self.m[0] + v.y * self.m[4] + v.z * self.m[8] + self.m[12];
+        let y = v.x * self.m[1] + v.y * self.m[5] + v.z * self.m[9] + self.m[13];
+        let z = v.x * self.m[2] + v.y * self.m[6] + v.z * self.m[10] + self.m[14];
+        let w = v.x * self.m[3] + v.y * self.m[7] + v.z * self.m[11] + self.m[15];
+        if w != 0.0 {
+            Vec3::new(x / w, y / w, z / w)
+        } else {
+            Vec3::new(x, y, z)
+        }
+    }
+
+    /// transform a direction (ignores translation, so normals/directions aren't
+    /// shifted by the matrix's translation column)
+    pub(crate) fn transform_direction(&self, v: Vec3) -> Vec3 {
+        Vec3::new(
+            v.x * self.m[0] + v.y * self.m[4] + v.z * self.m[8],
+            v.x * self.m[1] + v.y * self.m[5] + v.z * self.m[9],
+            v.x * self.m[2] + v.y * self.m[6] + v.z * self.m[10],
+        )
+    }
+}
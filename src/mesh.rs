@@ -0,0 +1,152 @@
+//! mesh data plus the built-in shapes and the wavefront obj loader
+
+use std::collections::HashSet;
+use std::fs;
+use std::io;
+
+use crate::math::Vec3;
+
+/// mesh data with vertices, the edge list used for wireframe drawing, and a
+/// triangle list (fan-triangulated from each face) used for solid rendering
+pub(crate) struct Mesh {
+    pub(crate) vertices: Vec<Vec3>,
+    pub(crate) edges: Vec<(usize, usize)>,
+    pub(crate) faces: Vec<(usize, usize, usize)>,
+}
+
+impl Mesh {
+    /// load a mesh from a wavefront .obj file, reading `v x y z` lines as vertices
+    /// and deriving the edge and triangle lists from `f ...` lines
+    pub(crate) fn from_obj(path: &str) -> io::Result<Mesh> {
+        let contents = fs::read_to_string(path)?;
+
+        let mut vertices = Vec::new();
+        let mut seen_edges = HashSet::new();
+        let mut edges = Vec::new();
+        let mut faces = Vec::new();
+
+        for line in contents.lines() {
+            let mut tokens = line.split_whitespace();
+            match tokens.next() {
+                Some("v") => {
+                    let coords: Vec<f32> = tokens.filter_map(|t| t.parse().ok()).collect();
+                    if coords.len() >= 3 {
+                        vertices.push(Vec3::new(coords[0], coords[1], coords[2]));
+                    }
+                }
+                Some("f") => {
+                    let face: Vec<usize> = tokens
+                        .filter_map(|t| parse_face_index(t, vertices.len()))
+                        .collect();
+
+                    // connect each vertex to the next, closing the polygon back to the start
+                    for i in 0..face.len() {
+                        let a = face[i];
+                        let b = face[(i + 1) % face.len()];
+                        let edge = if a < b { (a, b) } else { (b, a) };
+                        if seen_edges.insert(edge) {
+                            edges.push(edge);
+                        }
+                    }
+
+                    // fan-triangulate n-gons around the first vertex
+                    for i in 1..face.len().saturating_sub(1) {
+                        faces.push((face[0], face[i], face[i + 1]));
+                    }
+                }
+                _ => {}
+            }
+        }
+
+        Ok(Mesh { vertices, edges, faces })
+    }
+}
+
+/// parse a single `f` face token (`v`, `v/vt`, `v/vt/vn`, or `v//vn`) into a 0-based
+/// vertex index, resolving negative indices as relative to the vertices seen so far
+fn parse_face_index(token: &str, vertex_count: usize) -> Option<usize> {
+    let v: i64 = token.split('/').next()?.parse().ok()?;
+    if v > 0 {
+        Some((v - 1) as usize)
+    } else if v < 0 {
+        Some((vertex_count as i64 + v) as usize)
+    } else {
+        None
+    }
+}
+
+/// make cube mesh
+pub(crate) fn create_cube() -> Mesh {
+    Mesh {
+        vertices: vec![
+            Vec3::new(-1.0, -1.0, -1.0),
+            Vec3::new( 1.0, -1.0, -1.0),
+            Vec3::new( 1.0,  1.0, -1.0),
+            Vec3::new(-1.0,  1.0, -1.0),
+            Vec3::new(-1.0, -1.0,  1.0),
+            Vec3::new( 1.0, -1.0,  1.0),
+            Vec3::new( 1.0,  1.0,  1.0),
+            Vec3::new(-1.0,  1.0,  1.0),
+        ],
+        edges: vec![
+            (0, 1), (1, 2), (2, 3), (3, 0), // bottom
+            (4, 5), (5, 6), (6, 7), (7, 4), // top
+            (0, 4), (1, 5), (2, 6), (3, 7), // sides
+        ],
+        faces: vec![
+            (4, 5, 6), (4, 6, 7), // front (+z)
+            (0, 2, 1), (0, 3, 2), // back (-z)
+            (0, 1, 5), (0, 5, 4), // bottom (-y)
+            (3, 6, 2), (3, 7, 6), // top (+y)
+            (0, 4, 7), (0, 7, 3), // left (-x)
+            (1, 2, 6), (1, 6, 5), // right (+x)
+        ],
+    }
+}
+
+/// make pyramid mesh
+pub(crate) fn create_pyramid() -> Mesh {
+    Mesh {
+        vertices: vec![
+            Vec3::new( 0.0,  1.0,  0.0),    // top
+            Vec3::new(-1.0, -1.0, -1.0),    // base
+            Vec3::new( 1.0, -1.0, -1.0),
+            Vec3::new( 1.0, -1.0,  1.0),
+            Vec3::new(-1.0, -1.0,  1.0),
+        ],
+        edges: vec![
+            (1, 2), (2, 3), (3, 4), (4, 1), // base
+            (0, 1), (0, 2), (0, 3), (0, 4), // sides
+        ],
+        faces: vec![
+            (1, 2, 3), (1, 3, 4), // base (-y)
+            (0, 2, 1),            // side (-z)
+            (0, 3, 2),            // side (+x)
+            (0, 4, 3),            // side (+z)
+            (0, 1, 4),            // side (-x)
+        ],
+    }
+}
+
+/// make octahedron mesh
+pub(crate) fn create_octahedron() -> Mesh {
+    Mesh {
+        vertices: vec![
+            Vec3::new( 0.0,  1.0,  0.0),    // top
+            Vec3::new( 0.0, -1.0,  0.0),    // bottom
+            Vec3::new(-1.0,  0.0,  0.0),    // middle points
+            Vec3::new( 1.0,  0.0,  0.0),
+            Vec3::new( 0.0,  0.0, -1.0),
+            Vec3::new( 0.0,  0.0,  1.0),
+        ],
+        edges: vec![
+            (0, 2), (0, 3), (0, 4), (0, 5), // top edges
+            (1, 2), (1, 3), (1, 4), (1, 5), // bottom edges
+            (2, 4), (4, 3), (3, 5), (5, 2), // middle edges
+        ],
+        faces: vec![
+            (0, 4, 2), (0, 3, 4), (0, 5, 3), (0, 2, 5), // top
+            (1, 2, 4), (1, 4, 3), (1, 3, 5), (1, 5, 2), // bottom
+        ],
+    }
+}
@@ -0,0 +1,257 @@
+//! drawing mesh data into the pixel buffer: wireframe lines and, once a
+//! face list and depth buffer are available, solid lit triangles. also the
+//! raymarched signed-distance-field backend, which bypasses meshes entirely.
+
+use crate::camera::Camera;
+use crate::math::{Mat4, Vec3};
+use crate::mesh::Mesh;
+use crate::sdf::{self, Sdf};
+
+/// ambient term added to lambert shading so unlit faces aren't pure black
+const AMBIENT: f32 = 0.15;
+
+/// draw line in pixel buffer using bresenham
+/// https://www.youtube.com/watch?v=CceepU1vIKo
+/// color: 0xRRGGBB int (e.g. 0xffffff for white)
+fn draw_line(
+    buffer: &mut [u32],
+    width: usize,
+    height: usize,
+    x0: i32,
+    y0: i32,
+    x1: i32,
+    y1: i32,
+    color: u32,
+) {
+    let dx = (x1 - x0).abs();
+    let sx = if x0 < x1 { 1 } else { -1 };
+    let dy = -(y1 - y0).abs();
+    let sy = if y0 < y1 { 1 } else { -1 };
+    let mut err = dx + dy;
+
+    let (mut x, mut y) = (x0, y0);
+
+    loop {
+        if x >= 0 && x < width as i32 && y >= 0 && y < height as i32 {
+            buffer[y as usize * width + x as usize] = color;
+        }
+        if x == x1 && y == y1 {
+            break;
+        }
+        let e2 = 2 * err;
+        if e2 >= dy {
+            err += dy;
+            x += sx;
+        }
+        if e2 <= dx {
+            err += dx;
+            y += sy;
+        }
+    }
+}
+
+/// map an already-projected point (normalized device coordinates) to screen-space
+/// pixel coordinates (still float, for use in barycentric interpolation)
+fn to_screen(p: Vec3, width: usize, height: usize) -> (f32, f32) {
+    (
+        (p.x + 1.0) * 0.5 * width as f32,
+        (1.0 - p.y) * 0.5 * height as f32,
+    )
+}
+
+/// scale an 0xRRGGBB color by a lighting intensity in [0, 1]
+fn shade_color(color: u32, intensity: f32) -> u32 {
+    let r = (((color >> 16) & 0xff) as f32 * intensity) as u32;
+    let g = (((color >> 8) & 0xff) as f32 * intensity) as u32;
+    let b = ((color & 0xff) as f32 * intensity) as u32;
+    (r << 16) | (g << 8) | b
+}
+
+/// draw every edge of `mesh` as a line, after transforming by model, view and
+/// projection matrices
+pub(crate) fn draw_wireframe(
+    buffer: &mut [u32],
+    width: usize,
+    height: usize,
+    mesh: &Mesh,
+    model: &Mat4,
+    view: &Mat4,
+    projection: &Mat4,
+    color: u32,
+) {
+    for &(i0, i1) in &mesh.edges {
+        let v0 = mesh.vertices[i0];
+        let v1 = mesh.vertices[i1];
+
+        // model -> world -> camera -> screen
+        let c0 = view.transform_vec3(model.transform_vec3(v0));
+        let c1 = view.transform_vec3(model.transform_vec3(v1));
+        let p0 = projection.transform_vec3(c0);
+        let p1 = projection.transform_vec3(c1);
+
+        let (x0, y0) = to_screen(p0, width, height);
+        let (x1, y1) = to_screen(p1, width, height);
+
+        draw_line(buffer, width, height, x0 as i32, y0 as i32, x1 as i32, y1 as i32, color);
+    }
+}
+
+/// draw every face of `mesh` as a filled, lambert-shaded, depth-tested triangle,
+/// culling the faces whose winding shows their back to the camera. splits the
+/// frame into at most `thread_count` horizontal bands rasterized concurrently,
+/// one band per worker thread (falls back to the single-threaded path at 1).
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn draw_solid(
+    buffer: &mut [u32],
+    depth: &mut [f32],
+    width: usize,
+    height: usize,
+    mesh: &Mesh,
+    model: &Mat4,
+    view: &Mat4,
+    projection: &Mat4,
+    light_dir: Vec3,
+    base_color: u32,
+    thread_count: usize,
+) {
+    if thread_count <= 1 {
+        draw_solid_band(buffer, depth, 0, height, width, height, mesh, model, view, projection, light_dir, base_color);
+        return;
+    }
+
+    // round up so at most thread_count bands always cover the full height
+    let band_height = height.div_ceil(thread_count);
+
+    std::thread::scope(|scope| {
+        let buffer_bands = buffer.chunks_mut(width * band_height);
+        let depth_bands = depth.chunks_mut(width * band_height);
+        for (i, (buffer_band, depth_band)) in buffer_bands.zip(depth_bands).enumerate() {
+            let y_start = i * band_height;
+            let band_rows = buffer_band.len() / width;
+            scope.spawn(move || {
+                draw_solid_band(buffer_band, depth_band, y_start, band_rows, width, height, mesh, model, view, projection, light_dir, base_color);
+            });
+        }
+    });
+}
+
+/// rasterize every face of `mesh` into a single horizontal band: rows
+/// `y_start..y_start + band_rows` of a `width`x`height` frame, where `buffer`/`depth`
+/// are already-sliced to cover just that band (so row 0 of the slices is row
+/// `y_start` of the full frame)
+#[allow(clippy::too_many_arguments)]
+fn draw_solid_band(
+    buffer: &mut [u32],
+    depth: &mut [f32],
+    y_start: usize,
+    band_rows: usize,
+    width: usize,
+    height: usize,
+    mesh: &Mesh,
+    model: &Mat4,
+    view: &Mat4,
+    projection: &Mat4,
+    light_dir: Vec3,
+    base_color: u32,
+) {
+    let y_end = y_start + band_rows;
+
+    for &(i0, i1, i2) in &mesh.faces {
+        // model -> world space, used for lighting (the light is fixed in world space)
+        let w0 = model.transform_vec3(mesh.vertices[i0]);
+        let w1 = model.transform_vec3(mesh.vertices[i1]);
+        let w2 = model.transform_vec3(mesh.vertices[i2]);
+        let normal = w1.sub(w0).cross(w2.sub(w0)).normalize();
+
+        // world -> camera space, used for screen position and depth
+        let c0 = view.transform_vec3(w0);
+        let c1 = view.transform_vec3(w1);
+        let c2 = view.transform_vec3(w2);
+
+        let p0 = projection.transform_vec3(c0);
+        let p1 = projection.transform_vec3(c1);
+        let p2 = projection.transform_vec3(c2);
+
+        let (x0, y0) = to_screen(p0, width, height);
+        let (x1, y1) = to_screen(p1, width, height);
+        let (x2, y2) = to_screen(p2, width, height);
+
+        // signed screen-space area; since y grows downward, a front face (as
+        // wound in the mesh data, CCW seen from outside) projects to a negative
+        // area here, so anything >= 0 is facing away from the camera
+        let area = (x1 - x0) * (y2 - y0) - (x2 - x0) * (y1 - y0);
+        if area >= 0.0 {
+            continue;
+        }
+
+        let intensity = (normal.dot(light_dir).max(0.0) + AMBIENT).min(1.0);
+        let color = shade_color(base_color, intensity);
+
+        let min_x = x0.min(x1).min(x2).floor().max(0.0) as usize;
+        let max_x = x0.max(x1).max(x2).ceil().min(width as f32 - 1.0) as usize;
+        // clip the triangle's bounding box to this band's rows
+        let min_y = (y0.min(y1).min(y2).floor().max(0.0) as usize).max(y_start);
+        let max_y = (y0.max(y1).max(y2).ceil().min(height as f32 - 1.0) as usize).min(y_end.saturating_sub(1));
+        if min_y > max_y {
+            continue;
+        }
+
+        for py in min_y..=max_y {
+            for px in min_x..=max_x {
+                let (sx, sy) = (px as f32 + 0.5, py as f32 + 0.5);
+
+                // barycentric weights via the same edge function used for culling
+                let w0 = (x1 - sx) * (y2 - sy) - (x2 - sx) * (y1 - sy);
+                let w1 = (x2 - sx) * (y0 - sy) - (x0 - sx) * (y2 - sy);
+                let w2 = (x0 - sx) * (y1 - sy) - (x1 - sx) * (y0 - sy);
+
+                // pixel is inside the triangle when all three weights share area's sign
+                if !((w0 <= 0.0 && w1 <= 0.0 && w2 <= 0.0) || (w0 >= 0.0 && w1 >= 0.0 && w2 >= 0.0)) {
+                    continue;
+                }
+
+                let (b0, b1, b2) = (w0 / area, w1 / area, w2 / area);
+                let z = b0 * c0.z + b1 * c1.z + b2 * c2.z;
+
+                // index into this band's slice, so row y_start lands at index 0
+                let idx = (py - y_start) * width + px;
+                if z > depth[idx] {
+                    depth[idx] = z;
+                    buffer[idx] = color;
+                }
+            }
+        }
+    }
+}
+
+/// sphere-trace `scene` from `camera`'s point of view, one ray per pixel, shading
+/// hits with lambert + ambient against `light_dir`
+pub(crate) fn draw_sdf(
+    buffer: &mut [u32],
+    width: usize,
+    height: usize,
+    scene: &dyn Sdf,
+    camera: &Camera,
+    fov: f32,
+    aspect: f32,
+    light_dir: Vec3,
+    base_color: u32,
+) {
+    let forward = camera.forward();
+    let right = camera.right();
+    let up = camera.up();
+    let half_fov_tan = (fov / 2.0).tan();
+
+    for py in 0..height {
+        for px in 0..width {
+            let ndc_x = (2.0 * (px as f32 + 0.5) / width as f32 - 1.0) * aspect * half_fov_tan;
+            let ndc_y = (1.0 - 2.0 * (py as f32 + 0.5) / height as f32) * half_fov_tan;
+            let ray_dir = (right * ndc_x + up * ndc_y + forward).normalize();
+
+            if let Some((_, normal)) = sdf::march(scene, camera.pos, ray_dir) {
+                let intensity = (normal.dot(light_dir).max(0.0) + AMBIENT).min(1.0);
+                buffer[py * width + px] = shade_color(base_color, intensity);
+            }
+        }
+    }
+}
@@ -0,0 +1,111 @@
+//! signed-distance-field scenes rendered via sphere tracing, as an alternative
+//! to the mesh-based wireframe/solid backend
+//!
+//! not every combinator below is used by the demo scene in main.rs yet - allow
+//! dead_code rather than trim the api down to just today's uses
+#![allow(dead_code)]
+
+use crate::math::Vec3;
+
+/// max sphere-tracing steps before giving up and calling it a miss
+pub(crate) const MAX_STEPS: u32 = 100;
+/// stop marching (miss) once the ray has travelled this far
+pub(crate) const MAX_DIST: f32 = 50.0;
+/// distance below which a march step counts as a hit
+pub(crate) const EPSILON: f32 = 0.001;
+
+/// step size used to estimate the surface normal by central differences
+const NORMAL_EPSILON: f32 = 0.0005;
+
+/// a shape defined by its signed distance from any point in space
+pub(crate) trait Sdf {
+    /// signed distance from `p` to the surface: negative means inside
+    fn distance(&self, p: Vec3) -> f32;
+}
+
+pub(crate) struct Sphere {
+    pub(crate) center: Vec3,
+    pub(crate) radius: f32,
+}
+
+impl Sdf for Sphere {
+    fn distance(&self, p: Vec3) -> f32 {
+        (p - self.center).length() - self.radius
+    }
+}
+
+/// axis-aligned box with rounded corners/edges
+pub(crate) struct RoundBox {
+    pub(crate) center: Vec3,
+    pub(crate) half_extents: Vec3,
+    pub(crate) radius: f32,
+}
+
+impl Sdf for RoundBox {
+    fn distance(&self, p: Vec3) -> f32 {
+        let d = p - self.center;
+        let q = Vec3::new(
+            d.x.abs() - self.half_extents.x,
+            d.y.abs() - self.half_extents.y,
+            d.z.abs() - self.half_extents.z,
+        );
+        let outside = Vec3::new(q.x.max(0.0), q.y.max(0.0), q.z.max(0.0)).length();
+        let inside = q.x.max(q.y).max(q.z).min(0.0);
+        outside + inside - self.radius
+    }
+}
+
+pub(crate) struct Union<A: Sdf, B: Sdf>(pub(crate) A, pub(crate) B);
+
+impl<A: Sdf, B: Sdf> Sdf for Union<A, B> {
+    fn distance(&self, p: Vec3) -> f32 {
+        self.0.distance(p).min(self.1.distance(p))
+    }
+}
+
+pub(crate) struct Intersection<A: Sdf, B: Sdf>(pub(crate) A, pub(crate) B);
+
+impl<A: Sdf, B: Sdf> Sdf for Intersection<A, B> {
+    fn distance(&self, p: Vec3) -> f32 {
+        self.0.distance(p).max(self.1.distance(p))
+    }
+}
+
+/// `A` with `B` carved out of it
+pub(crate) struct Subtraction<A: Sdf, B: Sdf>(pub(crate) A, pub(crate) B);
+
+impl<A: Sdf, B: Sdf> Sdf for Subtraction<A, B> {
+    fn distance(&self, p: Vec3) -> f32 {
+        self.0.distance(p).max(-self.1.distance(p))
+    }
+}
+
+/// march `ray_dir` (unit length) from `ray_origin` through `scene`, returning the
+/// hit point and surface normal, or `None` if the ray escapes past `MAX_DIST`
+/// or runs out of steps without converging
+pub(crate) fn march(scene: &dyn Sdf, ray_origin: Vec3, ray_dir: Vec3) -> Option<(Vec3, Vec3)> {
+    let mut t = 0.0;
+    for _ in 0..MAX_STEPS {
+        let p = ray_origin + ray_dir * t;
+        let d = scene.distance(p);
+        if d < EPSILON {
+            return Some((p, estimate_normal(scene, p)));
+        }
+        t += d;
+        if t > MAX_DIST {
+            break;
+        }
+    }
+    None
+}
+
+/// central-difference gradient of the distance field, normalized into a surface normal
+fn estimate_normal(scene: &dyn Sdf, p: Vec3) -> Vec3 {
+    let e = NORMAL_EPSILON;
+    Vec3::new(
+        scene.distance(p + Vec3::new(e, 0.0, 0.0)) - scene.distance(p - Vec3::new(e, 0.0, 0.0)),
+        scene.distance(p + Vec3::new(0.0, e, 0.0)) - scene.distance(p - Vec3::new(0.0, e, 0.0)),
+        scene.distance(p + Vec3::new(0.0, 0.0, e)) - scene.distance(p - Vec3::new(0.0, 0.0, e)),
+    )
+    .normalize()
+}